@@ -49,24 +49,332 @@
 //! });
 //! 
 //! ```
+//!
+//! ### Thread-safe contexts
+//! `ctx_def!` stores the value behind a `static mut`, so sharing one across threads (or handing
+//! out an aliasing `&mut`) is undefined behaviour unless you wrap the inner struct in a mutex
+//! yourself. `ctx_def_sync!` does that for you: the value lives behind a `RwLock` in a regular
+//! `static`, and `ctx_req!`/`ctx_req_mut!`/`ctx_get!`/`ctx_get_mut!` work exactly the same way,
+//! except they now hand out RAII guards ([`CtxReadGuard`]/[`CtxWriteGuard`]) that release the
+//! lock when they go out of scope instead of raw references.
+//! ```rs
+//! use with_context::*;
+//!
+//! pub struct ExampleContext {
+//!     pub name: String,
+//! }
+//!
+//! ctx_def_sync!(ectx: ExampleContext);
+//!
+//! fn main() {
+//!     ctx_init!(ectx => { ExampleContext { name: String::from("Example Context") } });
+//!
+//!     // ctx_req! still works, but `ec` is now a read guard held for the block's duration.
+//!     ctx_req!(ec: ectx => {
+//!         println!("ExampleContext has name {}", ec.name);
+//!     });
+//! }
+//! ```
+//!
+//! ### Scoped overrides
+//! `ctx_scope!` temporarily shadows a `ctx_def!` context's value for the dynamic extent of a
+//! block, which is handy for tests, request-scoped config, and dependency injection. The override
+//! lives on a per-thread stack, so overrides on one thread are invisible to others, and they must
+//! unwind strictly LIFO - which the scope guard enforces even if the block panics. An override's
+//! allocation isn't freed when its scope exits, it's reused by the next `ctx_scope!` call instead,
+//! so a reference obtained from `ctx_get!` inside the scope stays valid (if stale) instead of
+//! dangling if it's kept around past it, and memory use stays bounded by the deepest nesting
+//! reached on a thread rather than growing with every call.
+//! ```rs
+//! use with_context::*;
+//!
+//! pub struct ExampleContext {
+//!     pub name: String,
+//! }
+//!
+//! ctx_def!(ectx: ExampleContext);
+//!
+//! fn main() {
+//!     ctx_init!(ectx => { ExampleContext { name: String::from("Example Context") } });
+//!
+//!     ctx_scope!(ectx => ExampleContext { name: String::from("Scoped Name") } => {
+//!         // Inside the scope, ctx_get!/ctx_req! see the overridden value.
+//!         ctx_req!(ec: ectx => {
+//!             println!("ExampleContext has name {}", ec.name);
+//!         });
+//!     });
+//!     // The override is popped here; ectx is back to "Example Context".
+//! }
+//! ```
+//!
+//! ### Fallible and in-place initialization
+//! `ctx_init!` can only take an infallible block and moves the constructed value into the slot.
+//! `ctx_try_init!` instead takes a block returning `Result<T, E>`, for init work that can fail
+//! (opening a file, binding a socket). `ctx_pin_init!` takes an in-place initializer - a closure
+//! `FnOnce(*mut T)` - for contexts that must not move after construction; pair it with
+//! `ctx_get_pin!`/`ctx_get_pin_mut!` to get a `Pin<&T>`/`Pin<&mut T>` back out. All three work
+//! against `ctx_def!` and `ctx_def_local!` contexts; `ctx_def_sync!` contexts don't support
+//! pinning, since access to them always goes through a `CtxReadGuard`/`CtxWriteGuard` instead.
+//! ```rust
+//! use with_context::*;
+//!
+//! pub struct ExampleContext {
+//!     pub name: String,
+//! }
+//!
+//! ctx_def!(ectx: ExampleContext);
+//!
+//! fn main() -> Result<(), std::io::Error> {
+//!     ctx_try_init!(ectx => {
+//!         Ok::<_, std::io::Error>(ExampleContext { name: String::from("Example Context") })
+//!     })?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Lifecycle: deinitialization and queries
+//! `ctx_get!` panics (in debug mode) on a context that hasn't been initialized yet.
+//! `ctx_is_init!` asks whether it has been, without that panic, in both build profiles,
+//! and `ctx_deinit!` takes the value out, runs its `Drop`, and resets the slot so the context can
+//! be initialized again later - useful for contexts holding OS resources with an explicit
+//! create/destroy lifecycle.
+//! ```rs
+//! use with_context::*;
+//!
+//! pub struct ExampleContext {
+//!     pub name: String,
+//! }
+//!
+//! ctx_def!(ectx: ExampleContext);
+//!
+//! fn main() {
+//!     assert!(!ctx_is_init!(ectx));
+//!     ctx_init!(ectx => { ExampleContext { name: String::from("Example Context") } });
+//!     assert!(ctx_is_init!(ectx));
+//!     ctx_deinit!(ectx);
+//!     assert!(!ctx_is_init!(ectx));
+//! }
+//! ```
+//!
+//! ### Per-thread contexts
+//! `ctx_def!` shares one `static mut` across every thread, which is wrong for contexts that are
+//! logically per-thread (a scratch buffer, an RNG, a current-task handle): handing a `&mut` to
+//! one thread while another reads it is a data race. `ctx_def_local!` gives each thread its own
+//! slot instead; `ctx_init!`/`ctx_req!`/`ctx_req_mut!`/`ctx_get!`/`ctx_get_mut!` all work exactly
+//! as before, they just transparently route to the calling thread's copy, and each thread
+//! initializes independently.
+//! ```rs
+//! use with_context::*;
+//!
+//! pub struct WorkerContext {
+//!     pub scratch: Vec<u8>,
+//! }
+//!
+//! ctx_def_local!(worker: WorkerContext);
+//!
+//! fn main() {
+//!     ctx_init!(worker => { WorkerContext { scratch: Vec::new() } });
+//!     ctx_req_mut!(w: worker => {
+//!         w.scratch.push(1);
+//!     });
+//! }
+//! ```
 
 #[macro_export]
 macro_rules! ctx_def {
     ($visibility:vis $name:ident: $($ty:tt)::*) => {
         $visibility mod $name {
+            // Every invocation generates the full dispatch surface the shared macros rely on,
+            // regardless of which subset a given caller actually uses - a non-`pub` module that
+            // only calls a few of them otherwise looks like dead code to rustc/clippy.
+            #![allow(dead_code)]
             use super::*;
-            pub static mut STATIC_CONTEXT: $crate::WithContext<$($ty)::*> = $crate::WithContext{context: None};
+            pub static mut STATIC_CONTEXT: $crate::WithContext<$($ty)::*> = $crate::WithContext::new();
+            pub unsafe fn init(value: $($ty)::*) {
+                (*::std::ptr::addr_of_mut!(STATIC_CONTEXT)).init(value);
+            }
+            // Popping a frame here only removes it from the *logical* stack, it never frees the
+            // frame's backing allocation - that keeps any `&'static` reference a caller already
+            // obtained from `get()`/`get_mut()` valid (if stale) instead of dangling once the
+            // scope that pushed it exits. The freed box is handed to `OVERRIDE_POOL` instead of
+            // being dropped, and the next `ctx_scope!` push reuses it rather than allocating
+            // again, so memory is bounded by the deepest nesting this thread has ever reached,
+            // not by the total number of `ctx_scope!` calls made over the program's lifetime.
+            ::std::thread_local! {
+                pub static OVERRIDES: ::std::cell::RefCell<Vec<::std::boxed::Box<$($ty)::*>>> = ::std::cell::RefCell::new(Vec::new());
+                pub static OVERRIDE_POOL: ::std::cell::RefCell<Vec<::std::boxed::Box<$($ty)::*>>> = ::std::cell::RefCell::new(Vec::new());
+            }
+            /// Pushes `value` onto the per-thread override stack for `ctx_scope!`, reusing a
+            /// box freed by a previously popped frame (see `OVERRIDE_POOL`) instead of allocating
+            /// a new one when one is available.
+            pub unsafe fn push_override(value: $($ty)::*) {
+                let reused = OVERRIDE_POOL.with(|pool| pool.borrow_mut().pop());
+                let boxed = match reused {
+                    Some(mut reused) => {
+                        *reused = value;
+                        reused
+                    }
+                    None => ::std::boxed::Box::new(value),
+                };
+                OVERRIDES.with(|overrides| overrides.borrow_mut().push(boxed));
+            }
+            /// Pops the top of the override stack, if any, and hands its box to `OVERRIDE_POOL`
+            /// for reuse rather than dropping it.
+            pub unsafe fn pop_override() {
+                OVERRIDES.with(|overrides| {
+                    if let Some(boxed) = overrides.borrow_mut().pop() {
+                        OVERRIDE_POOL.with(|pool| pool.borrow_mut().push(boxed));
+                    }
+                });
+            }
+            /// Returns the top of the per-thread override stack if one has been pushed by
+            /// `ctx_scope!`, falling back to the global context otherwise.
+            pub unsafe fn get() -> &'static $($ty)::* {
+                OVERRIDES.with(|overrides| {
+                    let borrowed = overrides.borrow();
+                    if let Some(top) = borrowed.last() {
+                        let ptr: *const $($ty)::* = &**top;
+                        return &*ptr;
+                    }
+                    drop(borrowed);
+                    (*::std::ptr::addr_of!(STATIC_CONTEXT)).get()
+                })
+            }
+            /// Mutable counterpart to [`get`]: mutably borrows the top of the override stack if
+            /// present, otherwise falls back to the global context.
+            pub unsafe fn get_mut() -> &'static mut $($ty)::* {
+                OVERRIDES.with(|overrides| {
+                    let mut borrowed = overrides.borrow_mut();
+                    if let Some(top) = borrowed.last_mut() {
+                        let ptr: *mut $($ty)::* = &mut **top;
+                        return &mut *ptr;
+                    }
+                    drop(borrowed);
+                    (*::std::ptr::addr_of_mut!(STATIC_CONTEXT)).get_mut()
+                })
+            }
+            // Pin-init and deinit below intentionally bypass `OVERRIDES`: they manage the global
+            // slot's own construction/destruction, which scoped overrides don't participate in.
+            pub unsafe fn init_pin(init: impl FnOnce(*mut $($ty)::*)) {
+                (*::std::ptr::addr_of_mut!(STATIC_CONTEXT)).init_pin(init);
+            }
+            pub unsafe fn get_pin() -> ::std::pin::Pin<&'static $($ty)::*> {
+                (*::std::ptr::addr_of!(STATIC_CONTEXT)).get_pin()
+            }
+            pub unsafe fn get_pin_mut() -> ::std::pin::Pin<&'static mut $($ty)::*> {
+                (*::std::ptr::addr_of_mut!(STATIC_CONTEXT)).get_pin_mut()
+            }
+            /// Unlike pin-init/deinit, this mirrors `get()`'s fallback order: a pushed override
+            /// makes the context "initialized" from the caller's point of view even though the
+            /// global slot underneath it may still be empty, so check `OVERRIDES` first.
+            pub unsafe fn is_init() -> bool {
+                OVERRIDES.with(|overrides| {
+                    if !overrides.borrow().is_empty() {
+                        return true;
+                    }
+                    (*::std::ptr::addr_of!(STATIC_CONTEXT)).is_init()
+                })
+            }
+            pub unsafe fn deinit() {
+                (*::std::ptr::addr_of_mut!(STATIC_CONTEXT)).deinit();
+            }
         }
     };
 }
+/// Temporarily shadows a context's value for the dynamic extent of `$body`, pushing `$value`
+/// onto the context's per-thread override stack and popping it again on the way out - including
+/// on panic, since the pop happens in a guard's `Drop`. Overrides are per-thread and strictly
+/// LIFO: `ctx_get!`/`ctx_get_mut!`/`ctx_req!`/`ctx_req_mut!` see the top of the stack while inside
+/// the scope, and `get_mut` on an override mutably borrows that top frame.
+///
+/// Popping a frame doesn't free its backing allocation immediately - it's handed to a per-path
+/// reuse pool instead (see `OVERRIDE_POOL` on the generated module), so a `&'static` reference
+/// obtained via `ctx_get!` while inside the scope and kept around past it stays valid (pointing
+/// at a now-stale value) instead of dangling. That matches the same "stale but not dangling" risk
+/// the crate already accepts for the plain `ctx_def!` global. Unlike freeing the allocation on
+/// every pop, reusing it means memory stays bounded by this thread's deepest concurrent `ctx_scope!`
+/// nesting rather than growing with the total number of calls made over the program's lifetime.
+#[macro_export]
+macro_rules! ctx_scope {
+    ($($path:ident)::+ => $value:expr => $body:block) => {{
+        let __ctx_scope_value = $value;
+        unsafe { $($path)::+::push_override(__ctx_scope_value) };
+        struct CtxScopeGuard;
+        impl ::std::ops::Drop for CtxScopeGuard {
+            fn drop(&mut self) {
+                unsafe { $($path)::+::pop_override() };
+            }
+        }
+        let _ctx_scope_guard = CtxScopeGuard;
+        $body
+    }};
+}
 #[macro_export]
 macro_rules! ctx_init {
+    ($($path:ident)::+ => $code:block) => {{
+        let __ctx_init_value = $code;
+        unsafe {
+            $($path)::+::init(__ctx_init_value);
+        }
+    }};
+}
+/// Fallible counterpart to `ctx_init!`: `$code` must evaluate to a `Result<T, E>`. On `Ok`, the
+/// value is stored and `Ok(())` is returned; on `Err`, the slot is left untouched (still
+/// uninitialized, if it held no value before) and the error is propagated, so recoverable init failures (opening a file, binding a socket) don't have to panic.
+#[macro_export]
+macro_rules! ctx_try_init {
     ($($path:ident)::+ => $code:block) => {
         unsafe {
-            $($path)::+::STATIC_CONTEXT.context = Some(
-                $code
-            );
+            match $code {
+                ::std::result::Result::Ok(value) => {
+                    $($path)::+::init(value);
+                    ::std::result::Result::Ok(())
+                }
+                ::std::result::Result::Err(err) => ::std::result::Result::Err(err),
+            }
+        }
+    };
+}
+/// In-place initialization for contexts that must not move after construction (self-referential
+/// data, or types embedding address-sensitive synchronization primitives). `$init` is a closure
+/// `FnOnce(*mut T)` that writes every field of the value through the given pointer; unlike
+/// `ctx_init!`, the constructed value is never produced as a temporary and moved into the slot.
+#[macro_export]
+macro_rules! ctx_pin_init {
+    ($($path:ident)::+ => $init:expr) => {{
+        let __ctx_pin_init_init = $init;
+        unsafe {
+            $($path)::+::init_pin(__ctx_pin_init_init);
         }
+    }};
+}
+/// Returns a `Pin<&T>` into an in-place-initialized context. See `ctx_pin_init!`.
+#[macro_export]
+macro_rules! ctx_get_pin {
+    ($($path:ident)::+) => {
+        unsafe { $($path)::+::get_pin() }
+    };
+}
+/// Returns a `Pin<&mut T>` into an in-place-initialized context. See `ctx_pin_init!`.
+#[macro_export]
+macro_rules! ctx_get_pin_mut {
+    ($($path:ident)::+) => {
+        unsafe { $($path)::+::get_pin_mut() }
+    };
+}
+/// Returns whether the context is currently initialized. Unlike `ctx_get!`, never panics.
+#[macro_export]
+macro_rules! ctx_is_init {
+    ($($path:ident)::+) => {
+        unsafe { $($path)::+::is_init() }
+    };
+}
+/// Takes the value out of the context, dropping it, and resets the slot to uninitialized so it
+/// can be initialized again later with `ctx_init!`/`ctx_try_init!`/`ctx_pin_init!`.
+#[macro_export]
+macro_rules! ctx_deinit {
+    ($($path:ident)::+) => {
+        unsafe { $($path)::+::deinit() }
     };
 }
 #[macro_export]
@@ -78,7 +386,7 @@ macro_rules! ctx_req {
     }) => {
         $visibility fn $name($($arg: $argt), *) $(-> $ret)? {
             $(
-                let $context = unsafe {$($path)::+::STATIC_CONTEXT.get()};
+                let $context = unsafe {$($path)::+::get()};
             )*
             $($body)*
         }
@@ -88,7 +396,7 @@ macro_rules! ctx_req {
     }) => {
         {
             $(
-                let $context = unsafe {$($path)::+::STATIC_CONTEXT.get()};
+                let $context = unsafe {$($path)::+::get()};
             )*
             $($body)*
         }
@@ -103,7 +411,7 @@ macro_rules! ctx_req_mut {
     }) => {
         $visibility fn $name($($arg: $argt), *) $(-> $ret)? {
             $(
-                let $context = unsafe {$($path)::+::STATIC_CONTEXT.get_mut()};
+                let mut $context = unsafe {$($path)::+::get_mut()};
             )*
             $($body)*
         }
@@ -113,7 +421,7 @@ macro_rules! ctx_req_mut {
     }) => {
         {
             $(
-                let $context = unsafe {$($path)::+::STATIC_CONTEXT.get_mut()};
+                let mut $context = unsafe {$($path)::+::get_mut()};
             )*
             $($body)*
         }
@@ -122,48 +430,541 @@ macro_rules! ctx_req_mut {
 #[macro_export]
 macro_rules! ctx_get {
     ($($path:ident)::+) => {
-        unsafe {$($path)::+::STATIC_CONTEXT.get()}
+        unsafe {$($path)::+::get()}
     };
 }
 #[macro_export]
 macro_rules! ctx_get_mut {
     ($($path:ident)::+) => {
-        unsafe {$($path)::+::STATIC_CONTEXT.get_mut()}
+        unsafe {$($path)::+::get_mut()}
     };
 }
 
+/// Backing storage for a [`ctx_def!`]/[`ctx_def_local!`] context. Holds `T` in place inside a
+/// [`std::mem::MaybeUninit`] slot (rather than an `Option<T>`) so that [`WithContext::init_pin`]
+/// can write directly into the slot's own memory - the value's address is fixed the moment `init`
+/// starts writing to it and never changes afterwards, which plain `Option<T>` can't promise since
+/// constructing a `Some(T)` requires building `T` somewhere first and moving it in.
 pub struct WithContext<T> {
-    pub context: Option<T>,
+    initialized: bool,
+    storage: std::mem::MaybeUninit<T>,
 }
 impl<T> WithContext<T> {
+    pub const fn new() -> Self {
+        Self {
+            initialized: false,
+            storage: std::mem::MaybeUninit::uninit(),
+        }
+    }
+    pub fn init(&mut self, value: T) {
+        self.init_pin(move |ptr| unsafe { ptr.write(value) });
+    }
     #[cfg(debug_assertions)]
     pub fn get(&self) -> &T {
-        match &self.context {
-            Some(t) => {
-                t
-            }
-            None => {
-                panic!("Context {} has not been initialized yet!", std::any::type_name::<T>())
-            }
+        if self.initialized {
+            unsafe { self.storage.assume_init_ref() }
+        } else {
+            panic!("Context {} has not been initialized yet!", std::any::type_name::<T>())
         }
     }
     #[cfg(not(debug_assertions))]
     pub fn get(&self) -> &T {
-        unsafe { self.context.as_ref().unwrap_unchecked() }
+        unsafe { self.storage.assume_init_ref() }
     }
     #[cfg(debug_assertions)]
     pub fn get_mut(&mut self) -> &mut T {
-        match &mut self.context {
-            Some(t) => {
-                t
+        if self.initialized {
+            unsafe { self.storage.assume_init_mut() }
+        } else {
+            panic!("Context '{}' has not been initialized yet!", std::any::type_name::<T>())
+        }
+    }
+    #[cfg(not(debug_assertions))]
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe { self.storage.assume_init_mut() }
+    }
+    /// Writes `T` in place by running `init` with a pointer into the slot's own (until now
+    /// uninitialized) storage, so the value's final address is the one `init` saw all along and
+    /// it is never subsequently moved. Safe so long as `init` fully initializes every field
+    /// before returning. If the slot already held a value, it's dropped first.
+    pub fn init_pin(&mut self, init: impl FnOnce(*mut T)) {
+        self.deinit();
+        init(self.storage.as_mut_ptr());
+        self.initialized = true;
+    }
+    /// Pinned view of the context. Sound as long as the slot is only ever populated once via
+    /// `ctx_pin_init!` and never reassigned afterwards.
+    pub fn get_pin(&self) -> std::pin::Pin<&T> {
+        unsafe { std::pin::Pin::new_unchecked(self.get()) }
+    }
+    /// Mutable pinned view of the context. See [`WithContext::get_pin`].
+    pub fn get_pin_mut(&mut self) -> std::pin::Pin<&mut T> {
+        unsafe { std::pin::Pin::new_unchecked(self.get_mut()) }
+    }
+    /// Non-panicking accessor: `None` if the context hasn't been initialized yet.
+    pub fn try_get(&self) -> Option<&T> {
+        self.initialized
+            .then(|| unsafe { self.storage.assume_init_ref() })
+    }
+    /// Whether the context currently holds a value. Never panics, in either build profile.
+    pub fn is_init(&self) -> bool {
+        self.initialized
+    }
+    /// Takes the value out of the slot, running its `Drop` impl, and resets the slot to
+    /// uninitialized so the context can be initialized again later.
+    pub fn deinit(&mut self) {
+        if self.initialized {
+            self.initialized = false;
+            unsafe { self.storage.assume_init_drop() };
+        }
+    }
+}
+impl<T> Default for WithContext<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> Drop for WithContext<T> {
+    fn drop(&mut self) {
+        self.deinit();
+    }
+}
+
+/// Defines a thread-safe context backend: the value lives behind a [`std::sync::RwLock`] in a
+/// regular `static` (never a `static mut`), so `ctx_req!`/`ctx_req_mut!`/`ctx_get!`/`ctx_get_mut!`
+/// hand out RAII [`CtxReadGuard`]/[`CtxWriteGuard`]s instead of raw references. Unlike `ctx_def!`,
+/// concurrent readers and writers are enforced at runtime rather than merely discouraged in the docs.
+///
+/// The generated functions are still marked `unsafe`, even though nothing in their bodies actually
+/// requires it, purely so every backend presents the same call convention - the shared macros
+/// (`ctx_init!`, `ctx_req!`, ...) always call through an `unsafe` block regardless of which backend
+/// `$path` resolves to, and a safe fn there would make that block spuriously "unnecessary".
+#[macro_export]
+macro_rules! ctx_def_sync {
+    ($visibility:vis $name:ident: $($ty:tt)::*) => {
+        $visibility mod $name {
+            // See the matching comment in `ctx_def!`: not every caller uses every generated fn.
+            #![allow(dead_code)]
+            use super::*;
+            pub static STATIC_CONTEXT: $crate::SyncWithContext<$($ty)::*> = $crate::SyncWithContext::new();
+            pub unsafe fn init(value: $($ty)::*) {
+                STATIC_CONTEXT.init(value);
+            }
+            pub unsafe fn get() -> $crate::CtxReadGuard<'static, $($ty)::*> {
+                STATIC_CONTEXT.get()
+            }
+            pub unsafe fn get_mut() -> $crate::CtxWriteGuard<'static, $($ty)::*> {
+                STATIC_CONTEXT.get_mut()
+            }
+            pub unsafe fn is_init() -> bool {
+                STATIC_CONTEXT.is_init()
             }
-            None => {
-                panic!("Context '{}' has not been initialized yet!", std::any::type_name::<T>())
+            pub unsafe fn deinit() {
+                STATIC_CONTEXT.deinit();
             }
         }
+    };
+}
+
+/// Defines a per-thread context backend: instead of the single `static mut` that `ctx_def!`
+/// shares across every thread, each thread gets its own independently-initialized slot, so
+/// handing out a `&mut` from one thread's context can never alias another thread's. Each thread
+/// must call `ctx_init!` itself; a missing init panics only on the thread that reads it (in debug
+/// mode), just like `ctx_def!`.
+#[macro_export]
+macro_rules! ctx_def_local {
+    ($visibility:vis $name:ident: $($ty:tt)::*) => {
+        $visibility mod $name {
+            // See the matching comment in `ctx_def!`: not every caller uses every generated fn.
+            #![allow(dead_code)]
+            use super::*;
+            ::std::thread_local! {
+                pub static STORAGE: ::std::cell::RefCell<$crate::WithContext<$($ty)::*>> =
+                    ::std::cell::RefCell::new($crate::WithContext::new());
+            }
+            pub unsafe fn init(value: $($ty)::*) {
+                STORAGE.with(|storage| storage.borrow_mut().init(value));
+            }
+            pub unsafe fn get() -> &'static $($ty)::* {
+                STORAGE.with(|storage| {
+                    let ptr: *const $($ty)::* = storage.borrow().get();
+                    &*ptr
+                })
+            }
+            pub unsafe fn get_mut() -> &'static mut $($ty)::* {
+                STORAGE.with(|storage| {
+                    let ptr: *mut $($ty)::* = storage.borrow_mut().get_mut();
+                    &mut *ptr
+                })
+            }
+            pub unsafe fn init_pin(init: impl FnOnce(*mut $($ty)::*)) {
+                STORAGE.with(|storage| storage.borrow_mut().init_pin(init));
+            }
+            pub unsafe fn get_pin() -> ::std::pin::Pin<&'static $($ty)::*> {
+                STORAGE.with(|storage| {
+                    let ptr: *const $($ty)::* = &*storage.borrow().get_pin();
+                    ::std::pin::Pin::new_unchecked(&*ptr)
+                })
+            }
+            pub unsafe fn get_pin_mut() -> ::std::pin::Pin<&'static mut $($ty)::*> {
+                STORAGE.with(|storage| {
+                    let ptr: *mut $($ty)::* = storage.borrow_mut().get_pin_mut().get_unchecked_mut();
+                    ::std::pin::Pin::new_unchecked(&mut *ptr)
+                })
+            }
+            pub unsafe fn is_init() -> bool {
+                STORAGE.with(|storage| storage.borrow().is_init())
+            }
+            pub unsafe fn deinit() {
+                STORAGE.with(|storage| storage.borrow_mut().deinit());
+            }
+        }
+    };
+}
+
+/// A synchronized counterpart to [`WithContext`]: the contained value is stored behind a
+/// [`std::sync::RwLock`] so it can be shared across threads and is only ever reachable through
+/// [`CtxReadGuard`]/[`CtxWriteGuard`], which enforce the borrow at runtime and release it on drop.
+pub struct SyncWithContext<T> {
+    context: std::sync::RwLock<Option<T>>,
+}
+impl<T> SyncWithContext<T> {
+    pub const fn new() -> Self {
+        Self {
+            context: std::sync::RwLock::new(None),
+        }
+    }
+    pub fn init(&self, value: T) {
+        *self.context.write().unwrap() = Some(value);
+    }
+    pub fn get(&self) -> CtxReadGuard<'_, T> {
+        CtxReadGuard {
+            guard: self.context.read().unwrap(),
+        }
+    }
+    pub fn get_mut(&self) -> CtxWriteGuard<'_, T> {
+        CtxWriteGuard {
+            guard: self.context.write().unwrap(),
+        }
+    }
+    /// Whether the context currently holds a value. Never panics.
+    pub fn is_init(&self) -> bool {
+        self.context
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .is_some()
+    }
+    /// Takes the value out of the slot, running its `Drop` impl, and resets the slot so the
+    /// context can be initialized again later.
+    pub fn deinit(&self) {
+        self.context.write().unwrap().take();
+    }
+}
+impl<T> Default for SyncWithContext<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// RAII read guard returned by [`SyncWithContext::get`]. Releases the read lock on drop.
+pub struct CtxReadGuard<'a, T> {
+    guard: std::sync::RwLockReadGuard<'a, Option<T>>,
+}
+impl<'a, T> std::ops::Deref for CtxReadGuard<'a, T> {
+    type Target = T;
+    #[cfg(debug_assertions)]
+    fn deref(&self) -> &T {
+        match &*self.guard {
+            Some(t) => t,
+            None => panic!("Context {} has not been initialized yet!", std::any::type_name::<T>()),
+        }
     }
     #[cfg(not(debug_assertions))]
-    pub fn get_mut(&mut self) -> &mut T {
-        unsafe { self.context.as_mut().unwrap_unchecked() }
+    fn deref(&self) -> &T {
+        unsafe { self.guard.as_ref().unwrap_unchecked() }
+    }
+}
+
+/// RAII write guard returned by [`SyncWithContext::get_mut`]. Releases the write lock on drop.
+pub struct CtxWriteGuard<'a, T> {
+    guard: std::sync::RwLockWriteGuard<'a, Option<T>>,
+}
+impl<'a, T> std::ops::Deref for CtxWriteGuard<'a, T> {
+    type Target = T;
+    #[cfg(debug_assertions)]
+    fn deref(&self) -> &T {
+        match &*self.guard {
+            Some(t) => t,
+            None => panic!("Context {} has not been initialized yet!", std::any::type_name::<T>()),
+        }
+    }
+    #[cfg(not(debug_assertions))]
+    fn deref(&self) -> &T {
+        unsafe { self.guard.as_ref().unwrap_unchecked() }
+    }
+}
+impl<'a, T> std::ops::DerefMut for CtxWriteGuard<'a, T> {
+    #[cfg(debug_assertions)]
+    fn deref_mut(&mut self) -> &mut T {
+        match &mut *self.guard {
+            Some(t) => t,
+            None => panic!("Context '{}' has not been initialized yet!", std::any::type_name::<T>()),
+        }
+    }
+    #[cfg(not(debug_assertions))]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.guard.as_mut().unwrap_unchecked() }
+    }
+}
+
+#[cfg(test)]
+mod pin_init_tests {
+    struct SelfReferential {
+        value: i32,
+        value_ptr: *const i32,
+    }
+
+    ctx_def!(selfref_ctx: SelfReferential);
+
+    #[test]
+    fn init_pin_writes_the_value_in_its_final_location() {
+        ctx_pin_init!(selfref_ctx => |ptr: *mut SelfReferential| unsafe {
+            std::ptr::addr_of_mut!((*ptr).value).write(42);
+            let value_ptr = std::ptr::addr_of!((*ptr).value);
+            std::ptr::addr_of_mut!((*ptr).value_ptr).write(value_ptr);
+        });
+
+        let pinned = ctx_get_pin!(selfref_ctx);
+        let final_value_addr: *const i32 = &pinned.value;
+        assert_eq!(pinned.value_ptr, final_value_addr);
+    }
+}
+
+#[cfg(test)]
+mod scope_tests {
+    pub struct ScopedContext {
+        pub name: String,
+    }
+
+    ctx_def!(scoped_ctx: ScopedContext);
+
+    #[test]
+    fn reference_taken_inside_a_scope_stays_valid_after_it_exits() {
+        ctx_init!(scoped_ctx => { ScopedContext { name: String::from("global") } });
+
+        let escaped: &'static ScopedContext = ctx_scope!(scoped_ctx => ScopedContext { name: String::from("scoped") } => {
+            ctx_get!(scoped_ctx)
+        });
+
+        // The scope has already exited and popped its override, but `escaped` must still point
+        // at readable memory instead of a freed `Box` - reading it must not segfault or panic.
+        assert_eq!(escaped.name, "scoped");
+        // New lookups after the scope exits fall back to the next frame (here, the global).
+        assert_eq!(ctx_get!(scoped_ctx).name, "global");
+    }
+}
+
+#[cfg(test)]
+mod lifecycle_cross_backend_tests {
+    pub struct LifecycleContext {
+        pub count: u32,
+    }
+
+    ctx_def_local!(local_ctx: LifecycleContext);
+    ctx_def_sync!(sync_ctx: LifecycleContext);
+
+    #[test]
+    fn is_init_and_deinit_work_against_a_ctx_def_local_context() {
+        assert!(!ctx_is_init!(local_ctx));
+        ctx_init!(local_ctx => { LifecycleContext { count: 1 } });
+        assert!(ctx_is_init!(local_ctx));
+        assert_eq!(ctx_get!(local_ctx).count, 1);
+        ctx_deinit!(local_ctx);
+        assert!(!ctx_is_init!(local_ctx));
+    }
+
+    #[test]
+    fn is_init_and_deinit_work_against_a_ctx_def_sync_context() {
+        assert!(!ctx_is_init!(sync_ctx));
+        ctx_init!(sync_ctx => { LifecycleContext { count: 1 } });
+        assert!(ctx_is_init!(sync_ctx));
+        ctx_req!(ctx: sync_ctx => { assert_eq!(ctx.count, 1); });
+        ctx_deinit!(sync_ctx);
+        assert!(!ctx_is_init!(sync_ctx));
+    }
+
+    pub struct PinnedLocalContext {
+        pub value: i32,
+    }
+
+    ctx_def_local!(pinned_local_ctx: PinnedLocalContext);
+
+    #[test]
+    fn pin_init_works_against_a_ctx_def_local_context() {
+        ctx_pin_init!(pinned_local_ctx => |ptr: *mut PinnedLocalContext| unsafe {
+            std::ptr::addr_of_mut!((*ptr).value).write(7);
+        });
+        assert_eq!(ctx_get_pin!(pinned_local_ctx).value, 7);
+    }
+}
+
+#[cfg(test)]
+mod sync_concurrency_tests {
+    pub struct Counter {
+        pub value: u32,
+    }
+
+    ctx_def_sync!(counter_ctx: Counter);
+
+    #[test]
+    fn a_reader_blocks_while_a_writer_holds_the_guard_and_proceeds_once_it_drops() {
+        ctx_init!(counter_ctx => { Counter { value: 1 } });
+
+        let (writer_holding_tx, writer_holding_rx) = std::sync::mpsc::channel();
+        let (release_writer_tx, release_writer_rx) = std::sync::mpsc::channel::<()>();
+        let (reader_done_tx, reader_done_rx) = std::sync::mpsc::channel();
+
+        let writer = std::thread::spawn(move || {
+            ctx_req_mut!(counter: counter_ctx => {
+                // Signal that this thread now holds the write guard, and hold it until told to let go, so the
+                // reader below has no way to observe the new value until this thread drops it.
+                writer_holding_tx.send(()).unwrap();
+                release_writer_rx.recv().unwrap();
+                counter.value = 2;
+            });
+        });
+
+        // Wait until the writer actually holds the write guard before starting the reader.
+        writer_holding_rx.recv().unwrap();
+
+        let reader = std::thread::spawn(move || {
+            let value = ctx_req!(counter: counter_ctx => { counter.value });
+            reader_done_tx.send(value).unwrap();
+        });
+
+        // The reader's ctx_req! should be blocked on the still-held write lock. This is a
+        // best-effort timing check - worst case it's a false pass, never a false failure, since a
+        // reader that *has* raced ahead would already show up in reader_done_rx.
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(
+            reader_done_rx.try_recv().is_err(),
+            "reader should still be blocked on the writer's guard"
+        );
+
+        release_writer_tx.send(()).unwrap();
+        writer.join().unwrap();
+
+        // Dropping the write guard must release the lock immediately - the reader can now
+        // proceed and observes the value the writer set.
+        assert_eq!(reader_done_rx.recv().unwrap(), 2);
+        reader.join().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod local_thread_isolation_tests {
+    pub struct WorkerContext {
+        pub id: u32,
+    }
+
+    ctx_def_local!(worker_ctx: WorkerContext);
+
+    #[test]
+    fn each_thread_has_its_own_independently_initialized_slot() {
+        ctx_init!(worker_ctx => { WorkerContext { id: 0 } });
+
+        let other_thread_saw = std::thread::spawn(|| {
+            // This thread has never called ctx_init!, so it must not observe the main thread's
+            // value - `ctx_is_init!` must report false here even though it's true on main.
+            let was_init_before = ctx_is_init!(worker_ctx);
+            ctx_init!(worker_ctx => { WorkerContext { id: 99 } });
+            (was_init_before, ctx_get!(worker_ctx).id)
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(other_thread_saw, (false, 99));
+        // The main thread's slot is untouched by the other thread's init.
+        assert_eq!(ctx_get!(worker_ctx).id, 0);
+    }
+}
+
+
+#[cfg(test)]
+mod scope_override_reuse_tests {
+    pub struct ScopedContext {
+        pub name: String,
+    }
+
+    ctx_def!(reused_ctx: ScopedContext);
+
+    #[test]
+    fn repeated_scopes_reuse_the_same_allocation_instead_of_leaking() {
+        ctx_init!(reused_ctx => { ScopedContext { name: String::from("global") } });
+
+        let mut addresses = Vec::new();
+        for i in 0..1000 {
+            ctx_scope!(reused_ctx => ScopedContext { name: format!("scoped-{i}") } => {
+                let scoped = ctx_get!(reused_ctx);
+                assert_eq!(scoped.name, format!("scoped-{i}"));
+                addresses.push(scoped as *const ScopedContext);
+            });
+        }
+
+        // If every call allocated its own box and never freed it, 1000 non-nested scopes would
+        // produce 1000 distinct addresses. With the pool, each pops into the same freed slot the
+        // next call reuses, so address churn stays bounded - one allocation gets reused, not a
+        // thousand leaked ones.
+        let distinct: std::collections::HashSet<_> = addresses.into_iter().collect();
+        assert_eq!(distinct.len(), 1, "expected every non-nested scope to reuse the same freed allocation");
+    }
+}
+
+#[cfg(test)]
+mod is_init_scope_tests {
+    pub struct ScopedContext {
+        pub name: String,
+    }
+
+    ctx_def!(scoped_is_init_ctx: ScopedContext);
+
+    #[test]
+    fn is_init_sees_an_active_override_even_if_the_global_is_empty() {
+        assert!(!ctx_is_init!(scoped_is_init_ctx));
+
+        ctx_scope!(scoped_is_init_ctx => ScopedContext { name: String::from("scoped") } => {
+            // The global slot was never initialized, but ctx_get! already succeeds here by
+            // reading the override - ctx_is_init! must agree, not report false while get() works.
+            assert!(ctx_is_init!(scoped_is_init_ctx));
+            assert_eq!(ctx_get!(scoped_is_init_ctx).name, "scoped");
+        });
+
+        assert!(!ctx_is_init!(scoped_is_init_ctx));
+    }
+}
+
+#[cfg(test)]
+mod scope_panic_safety_tests {
+    pub struct ScopedContext {
+        pub name: String,
+    }
+
+    ctx_def!(scoped_ctx: ScopedContext);
+
+    #[test]
+    fn scope_guard_pops_its_override_even_when_the_block_panics() {
+        ctx_init!(scoped_ctx => { ScopedContext { name: String::from("global") } });
+
+        let result = std::panic::catch_unwind(|| {
+            ctx_scope!(scoped_ctx => ScopedContext { name: String::from("scoped") } => {
+                assert_eq!(ctx_get!(scoped_ctx).name, "scoped");
+                panic!("boom");
+            });
+        });
+        assert!(result.is_err());
+
+        // The guard's Drop ran during unwinding, so the override is gone and lookups fall back
+        // to the global again.
+        assert_eq!(ctx_get!(scoped_ctx).name, "global");
     }
 }